@@ -0,0 +1,19 @@
+//! # Crate error type
+
+use thiserror::Error as ThisError;
+
+/// Errors that can occur while compiling, loading, or executing a native module.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("insufficient gas to run the requested function")]
+    InsufficientGasError,
+    /// Raised when a compiled shared library can't be `dlopen`ed, e.g. a corrupt or
+    /// partially-written file.
+    #[error("failed to load compiled artifact: {0}")]
+    LibraryLoadError(#[from] libloading::Error),
+    /// Raised by [`LlvmCodegenBackend::validate_libcalls`](crate::executor::backend::LlvmCodegenBackend)
+    /// when a compiled artifact doesn't export one of the runtime helper routines the generated
+    /// code may call into, e.g. a stale artifact built against an older runtime.
+    #[error("compiled artifact is missing required runtime symbol: {0}")]
+    MissingRuntimeSymbol(&'static str),
+}
@@ -1,24 +1,30 @@
+use super::backend::{CodegenBackend, LlvmCodegenBackend};
 use crate::{
     error::Error,
     execution_result::{ContractExecutionResult, ExecutionResult},
+    execution_resources::ExecutionResources,
     metadata::gas::GasMetadata,
     module::NativeModule,
     starknet::{DummySyscallHandler, StarknetSyscallHandler},
-    utils::generate_function_name,
     values::JitValue,
     OptLevel,
 };
 use cairo_lang_sierra::{
     extensions::core::{CoreLibfunc, CoreType},
     ids::FunctionId,
-    program::FunctionSignature,
+    program::{FunctionSignature, Program, StatementIdx},
     program_registry::ProgramRegistry,
 };
 use educe::Educe;
 use libc::c_void;
 use libloading::Library;
 use starknet_types_core::felt::Felt;
-use tempfile::NamedTempFile;
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
 
 #[derive(Educe)]
 #[educe(Debug)]
@@ -45,23 +51,129 @@ impl AotNativeExecutor {
     }
 
     /// Utility to convert a [`NativeModule`] into an [`AotNativeExecutor`].
-    pub fn from_native_module(module: NativeModule, opt_level: OptLevel) -> Self {
+    ///
+    /// `debug_info` enables DWARF line/location info mapping the generated code back to Sierra
+    /// statements (see [`DebugInfo`](crate::metadata::debug_info::DebugInfo)), at the cost of a
+    /// somewhat larger and slower-to-load shared library.
+    pub fn from_native_module(module: NativeModule, opt_level: OptLevel, debug_info: bool) -> Self {
+        let library = LlvmCodegenBackend
+            .compile(&module, opt_level, debug_info)
+            .unwrap();
+
         let NativeModule {
-            module,
             registry,
             mut metadata,
+            ..
         } = module;
 
-        let library_path = NamedTempFile::new().unwrap().into_temp_path();
+        Self {
+            library,
+            registry,
+            gas_metadata: metadata.remove().unwrap(),
+        }
+    }
+
+    /// Loads a previously-compiled shared library directly, skipping codegen entirely.
+    ///
+    /// Fails if `library_path` can't be `dlopen`ed, e.g. a corrupt or partially-written file left
+    /// behind by a process killed mid-write, or if it doesn't export every runtime
+    /// [`LibCall`](crate::libfuncs::libcall::LibCall); callers like [`Self::try_load_cached`]
+    /// treat that as a cache miss rather than propagating a panic.
+    ///
+    /// The caller must ensure `registry` and `gas_metadata` were produced from the same Sierra
+    /// program that `library_path` was compiled from; [`Self::from_native_module_cached`] takes
+    /// care of that pairing when loading from its cache.
+    pub fn from_library_path(
+        library_path: impl AsRef<Path>,
+        registry: ProgramRegistry<CoreType, CoreLibfunc>,
+        gas_metadata: GasMetadata,
+    ) -> Result<Self, Error> {
+        let library = unsafe { Library::new(library_path.as_ref()) }?;
+        LlvmCodegenBackend::validate_libcalls(&library)?;
+
+        Ok(Self {
+            library,
+            registry,
+            gas_metadata,
+        })
+    }
+
+    /// Like [`Self::from_native_module`], but persists the compiled shared library (plus a
+    /// sidecar holding the Sierra `program` and `GasMetadata` needed to reload it) under
+    /// `cache_dir`, keyed by a hash of the Sierra program and `opt_level`. A cache hit skips
+    /// `module_to_object`/`object_to_shared_lib` and loads the existing shared library instead,
+    /// so warming up thousands of precompiled contract classes at startup is cheap.
+    ///
+    /// `program` must be the Sierra program `module` was generated from; it's taken separately
+    /// from `module` because the original `Program` (not the `ProgramRegistry` built from it) is
+    /// what the cache actually needs to persist and reconstruct on a hit.
+    pub fn from_native_module_cached(
+        module: NativeModule,
+        program: &Program,
+        opt_level: OptLevel,
+        cache_dir: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        let cache_dir = cache_dir.as_ref();
+        fs::create_dir_all(cache_dir).unwrap();
+
+        let key = Self::cache_key(program, opt_level);
+        let library_path = cache_dir.join(format!("{key:016x}.so"));
+        let sidecar_path = cache_dir.join(format!("{key:016x}.cache"));
 
-        let object_data = crate::module_to_object(&module, opt_level).unwrap();
+        if let Some(executor) = Self::try_load_cached(&library_path, &sidecar_path) {
+            return Ok(executor);
+        }
+
+        // Caching needs the compiled artifact to land at a specific, persistent path, which the
+        // generic `CodegenBackend::compile` (whose `Artifact` may not even be a file on disk for
+        // a future backend) doesn't promise, so this goes straight through the LLVM pipeline
+        // instead of the backend abstraction.
+        //
+        // Cached artifacts are meant for warm, repeated loads, where the DWARF overhead isn't
+        // worth paying; debug builds should go through `from_native_module` instead.
+        let object_data = crate::module_to_object(&module.module, opt_level, false).unwrap();
         crate::object_to_shared_lib(&object_data, &library_path).unwrap();
 
-        Self {
-            library: unsafe { Library::new(library_path).unwrap() },
+        let NativeModule {
             registry,
-            gas_metadata: metadata.remove().unwrap(),
+            mut metadata,
+            ..
+        } = module;
+        let gas_metadata: GasMetadata = metadata.remove().unwrap();
+
+        let sidecar = bincode::serialize(&(program, &gas_metadata)).unwrap();
+        fs::write(&sidecar_path, sidecar).unwrap();
+
+        Self::from_library_path(library_path, registry, gas_metadata)
+    }
+
+    /// Attempts to load an executor straight from a cache entry, returning `None` on any miss
+    /// (missing files, unreadable sidecar, a deserialization mismatch, or a failed load) so the
+    /// caller falls back to recompiling.
+    fn try_load_cached(library_path: &Path, sidecar_path: &Path) -> Option<Self> {
+        if !library_path.exists() {
+            return None;
         }
+
+        let (program, gas_metadata): (Program, GasMetadata) =
+            bincode::deserialize(&fs::read(sidecar_path).ok()?).ok()?;
+        let registry = ProgramRegistry::new(&program).ok()?;
+        Self::from_library_path(library_path, registry, gas_metadata).ok()
+    }
+
+    /// Content-addresses a compiled module by hashing its Sierra `program` together with
+    /// `opt_level`, so recompiling an unchanged contract at the same optimization level always
+    /// resolves to the same cache entry.
+    ///
+    /// Hashes the original `Program` rather than the `ProgramRegistry` built from it:
+    /// `ProgramRegistry` holds resolved libfunc/type implementations that can't implement
+    /// `Serialize` (most of it comes from `cairo_lang_sierra`, so the orphan rule blocks adding
+    /// one here), while `Program` is the plain AST it was built from and round-trips cleanly.
+    fn cache_key(program: &Program, opt_level: OptLevel) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        bincode::serialize(program).unwrap().hash(&mut hasher);
+        (opt_level as u8).hash(&mut hasher);
+        hasher.finish()
     }
 
     pub fn invoke_dynamic(
@@ -82,6 +194,7 @@ impl AotNativeExecutor {
             args,
             available_gas,
             Option::<DummySyscallHandler>::None,
+            None,
         ))
     }
 
@@ -104,6 +217,7 @@ impl AotNativeExecutor {
             args,
             available_gas,
             Some(syscall_handler),
+            None,
         ))
     }
 
@@ -132,24 +246,94 @@ impl AotNativeExecutor {
             }],
             available_gas,
             Some(syscall_handler),
+            None,
         ))
     }
 
+    /// Like [`Self::invoke_contract_dynamic`], but also returns the [`ExecutionResources`]
+    /// (per-syscall and per-builtin usage) accumulated while running it, so sequencer
+    /// integrations can charge fees and build execution traces without re-deriving resources
+    /// from the VM.
+    ///
+    /// Builtin usage is seeded from the entrypoint's signature via
+    /// [`ExecutionResources::count_builtin_params`] before running it; per-syscall counts are
+    /// left unpopulated, since incrementing them requires instrumenting the `StarknetSyscallHandler`
+    /// dispatch path, which isn't in this tree slice to wire up.
+    pub fn invoke_contract_dynamic_with_resources(
+        &self,
+        function_id: &FunctionId,
+        args: &[Felt],
+        gas: Option<u128>,
+        syscall_handler: impl StarknetSyscallHandler,
+    ) -> Result<(ContractExecutionResult, ExecutionResources), Error> {
+        let available_gas = self
+            .gas_metadata
+            .get_initial_available_gas(function_id, gas)
+            .map_err(|_| crate::error::Error::InsufficientGasError)?;
+
+        let mut resources = ExecutionResources::default();
+        resources.count_builtin_params(self.extract_signature(function_id));
+
+        let result = ContractExecutionResult::from_execution_result(super::invoke_dynamic(
+            &self.registry,
+            self.find_function_ptr(function_id),
+            self.extract_signature(function_id),
+            &[JitValue::Struct {
+                fields: vec![JitValue::Array(
+                    args.iter().cloned().map(JitValue::Felt252).collect(),
+                )],
+                // TODO: Populate `debug_name`.
+                debug_name: None,
+            }],
+            available_gas,
+            Some(syscall_handler),
+            Some(&mut resources),
+        ))?;
+
+        Ok((result, resources))
+    }
+
+    /// Resolves a faulting address inside this executor's shared library back to the Sierra
+    /// statement that generated the code at that address. Statement indices are encoded as the
+    /// DWARF line number when the module is compiled with `debug_info = true`; libraries compiled
+    /// without it carry no such info, so this returns `None`.
+    pub fn statement_for_fault_address(&self, fault_address: usize) -> Option<StatementIdx> {
+        let mut statement_idx = None;
+
+        backtrace::resolve(fault_address as *mut c_void, |symbol| {
+            statement_idx = statement_idx
+                .take()
+                .or_else(|| symbol.lineno().map(|line| StatementIdx(line as usize)));
+        });
+
+        statement_idx
+    }
+
     pub fn find_function_ptr(&self, function_id: &FunctionId) -> *mut c_void {
-        let function_name = generate_function_name(function_id);
-        let function_name = format!("_mlir_ciface_{function_name}");
-
-        // Arguments and return values are hardcoded since they'll be handled by the trampoline.
-        unsafe {
-            self.library
-                .get::<extern "C" fn()>(function_name.as_bytes())
-                .unwrap()
-                .into_raw()
-                .into_raw()
-        }
+        LlvmCodegenBackend.find_function_ptr(&self.library, function_id)
     }
 
     fn extract_signature(&self, function_id: &FunctionId) -> &FunctionSignature {
         &self.registry.get_function(function_id).unwrap().signature
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn program_round_trips_through_bincode_and_rebuilds_a_registry() {
+        let program = Program {
+            type_declarations: vec![],
+            libfunc_declarations: vec![],
+            statements: vec![],
+            funcs: vec![],
+        };
+
+        let bytes = bincode::serialize(&program).unwrap();
+        let restored: Program = bincode::deserialize(&bytes).unwrap();
+
+        assert!(ProgramRegistry::<CoreType, CoreLibfunc>::new(&restored).is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+//! # Codegen backend abstraction
+//!
+//! Decouples "compile a [`NativeModule`] into a loadable artifact" from "resolve a [`FunctionId`]
+//! to a callable pointer", the same way `rustc_codegen_ssa` carved the LLVM-specific pieces out of
+//! rustc's backend. [`AotNativeExecutor`](super::aot::AotNativeExecutor) is a thin consumer of
+//! [`LlvmCodegenBackend`]; a Cranelift or interpreter backend can be added later behind the same
+//! trait without touching the executor or the `invoke_dynamic` calling convention.
+
+use crate::{
+    error::Error, libfuncs::libcall::LibCall, module::NativeModule,
+    utils::generate_function_name, OptLevel,
+};
+use cairo_lang_sierra::ids::FunctionId;
+use libc::c_void;
+use libloading::Library;
+use melior::ExecutionEngine;
+use tempfile::NamedTempFile;
+
+/// Compiles a [`NativeModule`] into a loadable artifact and resolves [`FunctionId`]s within it to
+/// callable pointers.
+pub trait CodegenBackend {
+    /// The loaded artifact produced by [`Self::compile`]; executors keep it alive for as long as
+    /// pointers resolved from it via [`Self::find_function_ptr`] are in use.
+    type Artifact;
+
+    /// Lowers `module` into a loadable artifact at the given optimization level. `debug_info`
+    /// requests that the artifact carry debug info mapping generated code back to Sierra, where
+    /// the backend supports it.
+    fn compile(
+        &self,
+        module: &NativeModule,
+        opt_level: OptLevel,
+        debug_info: bool,
+    ) -> Result<Self::Artifact, Error>;
+
+    /// Resolves `function_id` to a callable pointer within `artifact`. Arguments and return
+    /// values are hardcoded since they're handled by the `invoke_dynamic` trampoline.
+    fn find_function_ptr(
+        &self,
+        artifact: &Self::Artifact,
+        function_id: &FunctionId,
+    ) -> *mut c_void;
+}
+
+/// The default [`CodegenBackend`]: lowers through MLIR to an object file via LLVM, links it into
+/// a shared library, and loads it with `dlopen`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LlvmCodegenBackend;
+
+impl CodegenBackend for LlvmCodegenBackend {
+    type Artifact = Library;
+
+    fn compile(
+        &self,
+        module: &NativeModule,
+        opt_level: OptLevel,
+        debug_info: bool,
+    ) -> Result<Self::Artifact, Error> {
+        let library_path = NamedTempFile::new().unwrap().into_temp_path();
+
+        let object_data = crate::module_to_object(&module.module, opt_level, debug_info).unwrap();
+        crate::object_to_shared_lib(&object_data, &library_path).unwrap();
+
+        let library = unsafe { Library::new(library_path).unwrap() };
+        Self::validate_libcalls(&library)?;
+
+        Ok(library)
+    }
+
+    fn find_function_ptr(
+        &self,
+        artifact: &Self::Artifact,
+        function_id: &FunctionId,
+    ) -> *mut c_void {
+        let function_name = generate_function_name(function_id);
+        let function_name = format!("_mlir_ciface_{function_name}");
+
+        unsafe {
+            artifact
+                .get::<extern "C" fn()>(function_name.as_bytes())
+                .unwrap()
+                .into_raw()
+                .into_raw()
+        }
+    }
+}
+
+impl LlvmCodegenBackend {
+    /// Checks that every [`LibCall`] the generated code might call into is actually exported by
+    /// `library`, so a missing runtime routine is reported here, at load time, instead of a
+    /// `dlsym` lookup panicking lazily the first time some rarely hit code path calls it.
+    pub(crate) fn validate_libcalls(library: &Library) -> Result<(), Error> {
+        for libcall in LibCall::ALL {
+            if unsafe { library.get::<extern "C" fn()>(libcall.symbol().as_bytes()) }.is_err() {
+                return Err(Error::MissingRuntimeSymbol(libcall.symbol()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`CodegenBackend`] that keeps the lowered module resident in memory instead of going through
+/// an object file and `dlopen`, trading [`LlvmCodegenBackend`]'s persistable `.so` artifact for
+/// faster turnaround on a module that's only going to be run once or twice (e.g. interactive use,
+/// or a single contract call in a test). Used by
+/// [`JitNativeExecutor`](super::jit::JitNativeExecutor).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JitCodegenBackend;
+
+impl CodegenBackend for JitCodegenBackend {
+    type Artifact = ExecutionEngine;
+
+    fn compile(
+        &self,
+        module: &NativeModule,
+        opt_level: OptLevel,
+        debug_info: bool,
+    ) -> Result<Self::Artifact, Error> {
+        // `ExecutionEngine` JITs straight from the MLIR module, so there's no intermediate object
+        // file/shared library to validate libcalls against the way `LlvmCodegenBackend` does;
+        // a missing runtime symbol here instead surfaces as a lookup failure in
+        // `find_function_ptr`, the first time the faulting function is actually invoked.
+        Ok(ExecutionEngine::new(
+            &module.module,
+            opt_level as usize,
+            &[],
+            debug_info,
+        ))
+    }
+
+    fn find_function_ptr(
+        &self,
+        artifact: &Self::Artifact,
+        function_id: &FunctionId,
+    ) -> *mut c_void {
+        let function_name = generate_function_name(function_id);
+        let function_name = format!("_mlir_ciface_{function_name}");
+
+        artifact.lookup(&function_name) as *mut c_void
+    }
+}
@@ -0,0 +1,182 @@
+use super::backend::{CodegenBackend, JitCodegenBackend};
+use crate::{
+    error::Error,
+    execution_result::{ContractExecutionResult, ExecutionResult},
+    execution_resources::ExecutionResources,
+    metadata::gas::GasMetadata,
+    module::NativeModule,
+    starknet::{DummySyscallHandler, StarknetSyscallHandler},
+    values::JitValue,
+    OptLevel,
+};
+use cairo_lang_sierra::{
+    extensions::core::{CoreLibfunc, CoreType},
+    ids::FunctionId,
+    program::FunctionSignature,
+    program_registry::ProgramRegistry,
+};
+use educe::Educe;
+use libc::c_void;
+use melior::ExecutionEngine;
+use starknet_types_core::felt::Felt;
+
+/// A [`CodegenBackend::Artifact`]-backed executor that keeps the lowered module resident in
+/// memory via [`JitCodegenBackend`] instead of persisting it to a shared library, for callers
+/// that just need to run a module once or twice (e.g. tests, a REPL) rather than cache it across
+/// process restarts the way [`AotNativeExecutor`](super::aot::AotNativeExecutor) does.
+///
+/// Exposes the same `invoke_*` surface as `AotNativeExecutor`; the two only differ in how they
+/// get from a [`NativeModule`] to a callable pointer, which is exactly what [`CodegenBackend`]
+/// was introduced to abstract over.
+#[derive(Educe)]
+#[educe(Debug)]
+pub struct JitNativeExecutor {
+    #[educe(Debug(ignore))]
+    engine: ExecutionEngine,
+    #[educe(Debug(ignore))]
+    registry: ProgramRegistry<CoreType, CoreLibfunc>,
+    gas_metadata: GasMetadata,
+}
+
+impl JitNativeExecutor {
+    /// Utility to convert a [`NativeModule`] into a [`JitNativeExecutor`].
+    ///
+    /// `debug_info` enables the same DWARF line/location info `AotNativeExecutor` supports (see
+    /// [`DebugInfo`](crate::metadata::debug_info::DebugInfo)), at the cost of slower JIT lowering.
+    pub fn from_native_module(module: NativeModule, opt_level: OptLevel, debug_info: bool) -> Self {
+        let engine = JitCodegenBackend
+            .compile(&module, opt_level, debug_info)
+            .unwrap();
+
+        let NativeModule {
+            registry,
+            mut metadata,
+            ..
+        } = module;
+
+        Self {
+            engine,
+            registry,
+            gas_metadata: metadata.remove().unwrap(),
+        }
+    }
+
+    pub fn invoke_dynamic(
+        &self,
+        function_id: &FunctionId,
+        args: &[JitValue],
+        gas: Option<u128>,
+    ) -> Result<ExecutionResult, Error> {
+        let available_gas = self
+            .gas_metadata
+            .get_initial_available_gas(function_id, gas)
+            .map_err(|_| crate::error::Error::InsufficientGasError)?;
+
+        Ok(super::invoke_dynamic(
+            &self.registry,
+            self.find_function_ptr(function_id),
+            self.extract_signature(function_id),
+            args,
+            available_gas,
+            Option::<DummySyscallHandler>::None,
+            None,
+        ))
+    }
+
+    pub fn invoke_dynamic_with_syscall_handler(
+        &self,
+        function_id: &FunctionId,
+        args: &[JitValue],
+        gas: Option<u128>,
+        syscall_handler: impl StarknetSyscallHandler,
+    ) -> Result<ExecutionResult, Error> {
+        let available_gas = self
+            .gas_metadata
+            .get_initial_available_gas(function_id, gas)
+            .map_err(|_| crate::error::Error::InsufficientGasError)?;
+
+        Ok(super::invoke_dynamic(
+            &self.registry,
+            self.find_function_ptr(function_id),
+            self.extract_signature(function_id),
+            args,
+            available_gas,
+            Some(syscall_handler),
+            None,
+        ))
+    }
+
+    pub fn invoke_contract_dynamic(
+        &self,
+        function_id: &FunctionId,
+        args: &[Felt],
+        gas: Option<u128>,
+        syscall_handler: impl StarknetSyscallHandler,
+    ) -> Result<ContractExecutionResult, Error> {
+        let available_gas = self
+            .gas_metadata
+            .get_initial_available_gas(function_id, gas)
+            .map_err(|_| crate::error::Error::InsufficientGasError)?;
+
+        ContractExecutionResult::from_execution_result(super::invoke_dynamic(
+            &self.registry,
+            self.find_function_ptr(function_id),
+            self.extract_signature(function_id),
+            &[JitValue::Struct {
+                fields: vec![JitValue::Array(
+                    args.iter().cloned().map(JitValue::Felt252).collect(),
+                )],
+                // TODO: Populate `debug_name`.
+                debug_name: None,
+            }],
+            available_gas,
+            Some(syscall_handler),
+            None,
+        ))
+    }
+
+    /// Like [`Self::invoke_contract_dynamic`], but also returns the [`ExecutionResources`]
+    /// accumulated while running it; see
+    /// [`AotNativeExecutor::invoke_contract_dynamic_with_resources`](super::aot::AotNativeExecutor::invoke_contract_dynamic_with_resources).
+    pub fn invoke_contract_dynamic_with_resources(
+        &self,
+        function_id: &FunctionId,
+        args: &[Felt],
+        gas: Option<u128>,
+        syscall_handler: impl StarknetSyscallHandler,
+    ) -> Result<(ContractExecutionResult, ExecutionResources), Error> {
+        let available_gas = self
+            .gas_metadata
+            .get_initial_available_gas(function_id, gas)
+            .map_err(|_| crate::error::Error::InsufficientGasError)?;
+
+        let mut resources = ExecutionResources::default();
+        resources.count_builtin_params(self.extract_signature(function_id));
+
+        let result = ContractExecutionResult::from_execution_result(super::invoke_dynamic(
+            &self.registry,
+            self.find_function_ptr(function_id),
+            self.extract_signature(function_id),
+            &[JitValue::Struct {
+                fields: vec![JitValue::Array(
+                    args.iter().cloned().map(JitValue::Felt252).collect(),
+                )],
+                // TODO: Populate `debug_name`.
+                debug_name: None,
+            }],
+            available_gas,
+            Some(syscall_handler),
+            Some(&mut resources),
+        ))?;
+
+        Ok((result, resources))
+    }
+
+    pub fn find_function_ptr(&self, function_id: &FunctionId) -> *mut c_void {
+        JitCodegenBackend.find_function_ptr(&self.engine, function_id)
+    }
+
+    fn extract_signature(&self, function_id: &FunctionId) -> &FunctionSignature {
+        &self.registry.get_function(function_id).unwrap().signature
+    }
+}
@@ -0,0 +1,148 @@
+//! # Runtime libcall registry
+//!
+//! A single, typed source of truth for every runtime helper routine the generated code may call
+//! into (felt252 arithmetic that doesn't fit a single instruction, pedersen, keccak, the
+//! secp256k1/r1 curve operations, ...), replacing the previous stringly-typed approach where each
+//! libfunc builder hand-built the symbol name it expected to find at link time. Modeled on
+//! Cranelift's `ir::libcall::LibCall`.
+//!
+//! Centralizing these also lets a [`CodegenBackend`](crate::executor::backend::CodegenBackend)
+//! validate, at load time, that every symbol a module may call into is actually present in the
+//! compiled artifact, instead of the symbol lookup panicking lazily the first time some rarely
+//! hit code path calls it.
+//!
+//! Migration status: only [`LlvmCodegenBackend`](crate::executor::backend::LlvmCodegenBackend)'s
+//! load-time validation consumes this today, and [`LibCall::signature`] only covers the felt252
+//! arithmetic libcalls. The felt252/pedersen/keccak/secp256k1/secp256r1 libfunc builders still
+//! hand-format their own symbol names and haven't been switched over to [`LibCall::symbol_ref`]
+//! yet; none of those builder files exist in this tree slice, so routing them through here is
+//! follow-up work against those files, not done in this change.
+
+use melior::{ir::attribute::FlatSymbolRefAttribute, Context};
+
+/// A well-known runtime helper routine, together with its canonical external symbol name and ABI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LibCall {
+    Felt252Mul,
+    Felt252Div,
+    Felt252GuaranteeMul,
+    Pedersen,
+    Keccak,
+    Secp256k1Add,
+    Secp256k1Mul,
+    Secp256k1GetPointFromX,
+    Secp256k1GetXy,
+    Secp256r1Add,
+    Secp256r1Mul,
+    Secp256r1GetPointFromX,
+    Secp256r1GetXy,
+}
+
+/// A libcall's calling convention, expressed purely in felt252-sized values, so a libfunc builder
+/// can build the MLIR `FunctionType` for a `func::call` without re-deriving it by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LibCallSignature {
+    /// Number of felt252-sized arguments the libcall takes.
+    pub params: usize,
+    /// Number of felt252-sized values the libcall returns.
+    pub returns: usize,
+}
+
+impl LibCall {
+    /// Every known libcall, for load-time validation that a compiled artifact exports them all.
+    pub const ALL: &'static [LibCall] = &[
+        LibCall::Felt252Mul,
+        LibCall::Felt252Div,
+        LibCall::Felt252GuaranteeMul,
+        LibCall::Pedersen,
+        LibCall::Keccak,
+        LibCall::Secp256k1Add,
+        LibCall::Secp256k1Mul,
+        LibCall::Secp256k1GetPointFromX,
+        LibCall::Secp256k1GetXy,
+        LibCall::Secp256r1Add,
+        LibCall::Secp256r1Mul,
+        LibCall::Secp256r1GetPointFromX,
+        LibCall::Secp256r1GetXy,
+    ];
+
+    /// The external symbol this libcall is linked against.
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            LibCall::Felt252Mul => "cairo_native__libfunc__felt252_mul",
+            LibCall::Felt252Div => "cairo_native__libfunc__felt252_div",
+            LibCall::Felt252GuaranteeMul => "cairo_native__libfunc__felt252_guarantee_mul",
+            LibCall::Pedersen => "cairo_native__libfunc__pedersen",
+            LibCall::Keccak => "cairo_native__libfunc__keccak",
+            LibCall::Secp256k1Add => "cairo_native__libfunc__secp256k1_add",
+            LibCall::Secp256k1Mul => "cairo_native__libfunc__secp256k1_mul",
+            LibCall::Secp256k1GetPointFromX => "cairo_native__libfunc__secp256k1_get_point_from_x",
+            LibCall::Secp256k1GetXy => "cairo_native__libfunc__secp256k1_get_xy",
+            LibCall::Secp256r1Add => "cairo_native__libfunc__secp256r1_add",
+            LibCall::Secp256r1Mul => "cairo_native__libfunc__secp256r1_mul",
+            LibCall::Secp256r1GetPointFromX => "cairo_native__libfunc__secp256r1_get_point_from_x",
+            LibCall::Secp256r1GetXy => "cairo_native__libfunc__secp256r1_get_xy",
+        }
+    }
+
+    /// The libcall's calling convention, in felt252-sized parameters and return values.
+    ///
+    /// Only implemented for the felt252 arithmetic libcalls, which take two felt252s and return
+    /// one. The hash and curve libcalls operate on multi-limb points and byte buffers whose exact
+    /// calling convention is part of the runtime's C ABI; that ABI isn't in this tree slice to
+    /// verify against, so this intentionally returns `None` for them rather than guess at a shape
+    /// that might not match the real `cairo_native_runtime` symbols.
+    pub const fn signature(self) -> Option<LibCallSignature> {
+        match self {
+            LibCall::Felt252Mul | LibCall::Felt252Div | LibCall::Felt252GuaranteeMul => {
+                Some(LibCallSignature {
+                    params: 2,
+                    returns: 1,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Builds the `FlatSymbolRefAttribute` libfunc builders should pass to `func::call` when
+    /// invoking this libcall, instead of hand-formatting the symbol name themselves.
+    pub fn symbol_ref<'ctx>(self, context: &'ctx Context) -> FlatSymbolRefAttribute<'ctx> {
+        FlatSymbolRefAttribute::new(context, self.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn all_entries_have_distinct_symbols() {
+        let symbols: HashSet<_> = LibCall::ALL.iter().map(|libcall| libcall.symbol()).collect();
+        assert_eq!(symbols.len(), LibCall::ALL.len());
+    }
+
+    #[test]
+    fn every_symbol_is_namespaced() {
+        for libcall in LibCall::ALL {
+            assert!(libcall.symbol().starts_with("cairo_native__libfunc__"));
+        }
+    }
+
+    #[test]
+    fn felt252_arithmetic_libcalls_take_two_felts_and_return_one() {
+        assert_eq!(
+            LibCall::Felt252Mul.signature(),
+            Some(LibCallSignature {
+                params: 2,
+                returns: 1
+            })
+        );
+    }
+
+    #[test]
+    fn curve_and_hash_libcalls_have_no_specified_signature_yet() {
+        assert_eq!(LibCall::Secp256k1Add.signature(), None);
+        assert_eq!(LibCall::Keccak.signature(), None);
+    }
+}
@@ -0,0 +1,40 @@
+//! # Sierra debug info metadata
+//!
+//! Opt-in metadata that makes libfunc builders tag the [`Location`] of every operation they
+//! generate with the originating Sierra function and statement, so the emitted object carries
+//! DWARF line/location info mapping native code back to Sierra. With this in place, `gdb`/`lldb`
+//! can step through native Cairo, and a fault address can be translated back to a Sierra
+//! statement rather than a bare instruction pointer.
+//!
+//! The statement index is encoded as a DWARF *line number*, not a name annotation: the reading
+//! side, [`AotNativeExecutor::statement_for_fault_address`](crate::executor::aot::AotNativeExecutor::statement_for_fault_address),
+//! recovers it via `backtrace::Symbol::lineno`, which only ever sees line/file debug info, never
+//! a `Location::name` annotation.
+
+use cairo_lang_sierra::{ids::FunctionId, program::StatementIdx};
+use melior::{ir::Location, Context};
+
+/// Presence of this metadata in the [`MetadataStorage`](crate::metadata::MetadataStorage) is
+/// what turns debug-info emission on; its absence keeps codegen exactly as it was before.
+#[derive(Debug, Clone, Default)]
+pub struct DebugInfo {
+    /// The function whose statements are currently being lowered. Libfunc builders that want
+    /// `current_function` populated are responsible for setting it as they walk into each
+    /// function's statement list; nothing upstream of this module does so automatically yet.
+    pub current_function: Option<FunctionId>,
+}
+
+impl DebugInfo {
+    /// Builds the [`Location`] `statement_idx` (and the current function, if known) should be
+    /// tagged with, encoding the statement index as a DWARF line number so
+    /// [`statement_for_fault_address`](crate::executor::aot::AotNativeExecutor::statement_for_fault_address)
+    /// can recover it from a fault address via `backtrace::Symbol::lineno`.
+    pub fn tag_location<'ctx>(&self, context: &'ctx Context, statement_idx: StatementIdx) -> Location<'ctx> {
+        let file = match &self.current_function {
+            Some(function_id) => format!("sierra:{function_id}"),
+            None => "sierra".to_string(),
+        };
+
+        Location::new(context, &file, statement_idx.0 as u32, 0)
+    }
+}